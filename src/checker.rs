@@ -0,0 +1,391 @@
+use crate::parser::{Link, LinkKind};
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+/// Outcome of validating a single extracted [`Link`].
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum LinkStatus {
+    /// The target resolved and responded (or exists on disk).
+    Ok,
+    /// The target could not be resolved, or responded with an error status.
+    Broken,
+    /// The link matched an exception pattern or carries a scheme `check_links` does not
+    /// know how to validate, and was not checked.
+    Skipped,
+}
+
+/// Result of validating a single [`Link`] produced by [`extract_links`](crate::parser::extract_links).
+///
+/// The fields of the source `Link` are inlined directly rather than nested under a
+/// `link` key (or `#[serde(flatten)]`ed), because the `csv` crate can only serialize a
+/// flat record of scalar fields and errors on a nested struct or a flattened one.
+///
+/// # Fields
+/// * `description`, `url`, `source_file`, `kind`, `is_external`, `resolved_target` - Copied
+///   from the original `Link`; see [`Link`] for their meaning.
+/// * `checked_target` - The absolute URL or filesystem path the link was checked against.
+/// * `status` - Whether the target was reachable, broken, or skipped.
+/// * `detail` - Human-readable detail about the outcome (status code, io error, exception reason).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct LinkReport {
+    pub description: String,
+    pub url: String,
+    pub source_file: String,
+    pub kind: LinkKind,
+    pub is_external: bool,
+    pub resolved_target: Option<String>,
+    pub checked_target: String,
+    pub status: LinkStatus,
+    pub detail: String,
+}
+
+/// Builds a [`LinkReport`] for `link`, copying its fields inline and filling in the
+/// check-specific ones.
+fn build_report(
+    link: &Link,
+    checked_target: String,
+    status: LinkStatus,
+    detail: String,
+) -> LinkReport {
+    LinkReport {
+        description: link.description.clone(),
+        url: link.url.clone(),
+        source_file: link.source_file.clone(),
+        kind: link.kind.clone(),
+        is_external: link.is_external,
+        resolved_target: link.resolved_target.clone(),
+        checked_target,
+        status,
+        detail,
+    }
+}
+
+/// Options controlling how [`check_links`] validates a batch of links.
+///
+/// # Fields
+/// * `exceptions` - Glob patterns or literal URLs that are never reported as broken.
+/// * `max_concurrency` - Maximum number of links checked at the same time.
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    pub exceptions: Vec<String>,
+    pub max_concurrency: usize,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        CheckOptions {
+            exceptions: Vec::new(),
+            max_concurrency: 8,
+        }
+    }
+}
+
+/// Returns `true` if `url` matches one of the `exceptions` (glob patterns or literal strings).
+fn is_exception(url: &str, exceptions: &[String]) -> bool {
+    exceptions.iter().any(|pattern| {
+        pattern == url
+            || Pattern::new(pattern)
+                .map(|glob| glob.matches(url))
+                .unwrap_or(false)
+    })
+}
+
+/// Returns `true` if `url` carries a network scheme handled by the HTTP checker.
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Returns `true` if `url` carries a scheme neither `check_http` nor `check_filesystem`
+/// knows how to validate (e.g. `mailto:`, `tel:`), as opposed to a `file:`/relative path
+/// or an `http(s)` URL.
+fn is_unsupported_scheme(url: &str) -> bool {
+    crate::parser::is_external_url(url) && !is_http_url(url) && !url.starts_with("file://")
+}
+
+/// Resolves a relative or `file:` link target against the directory containing `source_file`.
+///
+/// Prefers `link.resolved_target`, which `parser::resolve_target` already computed from the
+/// link's raw (pre-percent-encoding) target, so e.g. a heading-anchored wikilink's `#anchor`
+/// is stripped correctly instead of being looked for as a literal `%23` in a filename. Falls
+/// back to stripping a `file://` prefix from `link.url` directly, since `resolve_target`
+/// treats any scheme-bearing URL (including `file://`) as external and leaves it `None`.
+fn resolve_filesystem_target(link: &Link) -> PathBuf {
+    if let Some(resolved) = &link.resolved_target {
+        return PathBuf::from(resolved);
+    }
+
+    let raw = link
+        .url
+        .strip_prefix("file://")
+        .unwrap_or(&link.url)
+        .split('#')
+        .next()
+        .unwrap_or("");
+    let base = Path::new(&link.source_file)
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+    base.join(crate::parser::percent_decode(raw))
+}
+
+/// Checks a `file:`/relative `Link` by asserting its resolved target exists on disk,
+/// mirroring the approach used by rustc's linkchecker.
+fn check_filesystem(link: &Link) -> LinkReport {
+    let target = resolve_filesystem_target(link);
+    let status = if target.exists() {
+        LinkStatus::Ok
+    } else {
+        LinkStatus::Broken
+    };
+    let detail = if target.exists() {
+        "target exists".to_string()
+    } else {
+        "target not found on disk".to_string()
+    };
+    build_report(link, target.to_string_lossy().into_owned(), status, detail)
+}
+
+/// Connect timeout for [`http_agent`]'s requests.
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Overall per-request timeout (connect + write + read) for [`http_agent`]'s requests.
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Returns a shared [`ureq::Agent`] with bounded connect/request timeouts, so one
+/// unreachable or slow host can't hang its worker thread forever and stall
+/// [`check_links`]'s whole batch once all `max_concurrency` workers are stuck on dead hosts.
+fn http_agent() -> &'static ureq::Agent {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+    AGENT.get_or_init(|| {
+        ureq::AgentBuilder::new()
+            .timeout_connect(HTTP_CONNECT_TIMEOUT)
+            .timeout(HTTP_REQUEST_TIMEOUT)
+            .build()
+    })
+}
+
+/// Checks an `http`/`https` `Link` with a HEAD request, falling back to GET when the
+/// server rejects HEAD (some servers only implement GET).
+///
+/// Requires `ureq` 2.x: the HEAD→GET fallback and `Broken` classification below depend on
+/// `.call()` returning `Err(ureq::Error::Status(code, _))` for non-2xx/3xx responses, which
+/// is 2.x's behavior. `ureq` 3.x returns `Ok(response)` for HTTP error statuses by default
+/// and restructures `Error`, so this function would need rewriting against
+/// `response.status()` directly before upgrading past 2.x. (This tree has no
+/// `Cargo.toml`/`Cargo.lock` to pin the dependency against, so this is a contract on the
+/// version this code is written for, not something enforced by the build.)
+fn check_http(link: &Link) -> LinkReport {
+    let agent = http_agent();
+    let outcome = match agent.head(&link.url).call() {
+        Ok(response) => Ok(response.status()),
+        Err(ureq::Error::Status(_, _)) => match agent.get(&link.url).call() {
+            Ok(response) => Ok(response.status()),
+            Err(ureq::Error::Status(code, _)) => Ok(code),
+            Err(e) => Err(e.to_string()),
+        },
+        Err(e) => Err(e.to_string()),
+    };
+
+    let (status, detail) = match outcome {
+        Ok(code) if (200..400).contains(&code) => (LinkStatus::Ok, code.to_string()),
+        Ok(code) => (LinkStatus::Broken, code.to_string()),
+        Err(e) => (LinkStatus::Broken, e),
+    };
+
+    build_report(link, link.url.clone(), status, detail)
+}
+
+/// Validates a single link, dispatching to the HTTP or filesystem checker and honoring
+/// the configured exception patterns. Links carrying a scheme neither checker supports
+/// (e.g. `mailto:`, `tel:`) are reported as `Skipped` rather than treated as a filesystem
+/// path, since `mailto:a@b.com` is never going to exist on disk.
+fn check_one(link: &Link, options: &CheckOptions) -> LinkReport {
+    if is_exception(&link.url, &options.exceptions) {
+        return build_report(
+            link,
+            link.url.clone(),
+            LinkStatus::Skipped,
+            "matched exception pattern".to_string(),
+        );
+    }
+
+    if is_http_url(&link.url) {
+        check_http(link)
+    } else if is_unsupported_scheme(&link.url) {
+        build_report(
+            link,
+            link.url.clone(),
+            LinkStatus::Skipped,
+            "unsupported URL scheme".to_string(),
+        )
+    } else {
+        check_filesystem(link)
+    }
+}
+
+/// Validates a batch of [`Link`]s, classifying each as [`LinkStatus::Ok`], [`LinkStatus::Broken`],
+/// or [`LinkStatus::Skipped`].
+///
+/// `http`/`https` URLs are checked with a HEAD (falling back to GET) request; relative and
+/// `file:` URLs are resolved against their link's `source_file` directory and checked for
+/// existence on disk. URLs carrying any other scheme (`mailto:`, `tel:`, ...) are reported
+/// as `Skipped`, since neither checker can validate them. Work is spread across up to
+/// `options.max_concurrency` threads so large batches don't open thousands of sockets at once.
+///
+/// # Inputs
+///
+/// - `links`: Slice of `Link`s to validate, typically produced by `extract_links`.
+/// - `options`: `CheckOptions` controlling exception patterns and concurrency.
+///
+/// # Results
+///
+/// - `Vec<LinkReport>` in the same order as `links`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let reports = check_links(&links, &CheckOptions::default());
+/// ```
+pub fn check_links(links: &[Link], options: &CheckOptions) -> Vec<LinkReport> {
+    if links.is_empty() {
+        return Vec::new();
+    }
+
+    let worker_count = options.max_concurrency.max(1).min(links.len());
+    let chunk_size = links.len().div_ceil(worker_count);
+
+    thread::scope(|scope| {
+        links
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(|| {
+                    chunk
+                        .iter()
+                        .map(|link| check_one(link, options))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(url: &str, source_file: &str) -> Link {
+        Link {
+            description: String::new(),
+            is_external: crate::parser::is_external_url(url),
+            resolved_target: crate::parser::resolve_target(url, source_file),
+            url: url.to_string(),
+            source_file: source_file.to_string(),
+            kind: LinkKind::Inline,
+        }
+    }
+
+    #[test]
+    fn filesystem_link_to_existing_file_is_ok() {
+        let l = link("three_links.md", "test_markdown/no_links.md");
+        let report = check_filesystem(&l);
+        assert_eq!(report.status, LinkStatus::Ok);
+    }
+
+    #[test]
+    fn filesystem_link_to_missing_file_is_broken() {
+        let l = link("does_not_exist.md", "test_markdown/no_links.md");
+        let report = check_filesystem(&l);
+        assert_eq!(report.status, LinkStatus::Broken);
+    }
+
+    /// Tests that a `file://` link's fallback resolution path percent-decodes its target,
+    /// so a CommonMark-escaped space resolves to the real filename rather than a literal
+    /// `%20` path that can never exist on disk.
+    #[test]
+    fn file_scheme_link_resolves_percent_encoded_space() {
+        let l = Link {
+            description: String::new(),
+            url: "file://My%20Notes.md".to_string(),
+            source_file: "docs/index.md".to_string(),
+            kind: LinkKind::Inline,
+            is_external: true,
+            resolved_target: None,
+        };
+        assert_eq!(
+            resolve_filesystem_target(&l),
+            PathBuf::from("docs/My Notes.md")
+        );
+    }
+
+    /// Tests that a heading-anchored wikilink (`[[three_links#Install]]`) resolves to the
+    /// note itself and is reported `Ok`, rather than being looked up as a literal
+    /// `three_links%23Install` file on disk.
+    #[test]
+    fn wikilink_heading_anchor_resolves_to_existing_file() {
+        let l = Link {
+            description: "Install".to_string(),
+            url: "three_links.md%23Install".to_string(),
+            source_file: "test_markdown/no_links.md".to_string(),
+            kind: LinkKind::Wikilink,
+            is_external: false,
+            resolved_target: crate::parser::resolve_target(
+                "three_links.md#Install",
+                "test_markdown/no_links.md",
+            ),
+        };
+        let report = check_filesystem(&l);
+        assert_eq!(report.status, LinkStatus::Ok);
+    }
+
+    #[test]
+    fn exception_pattern_skips_link() {
+        let l = link("https://example.com/anything", "file.md");
+        let options = CheckOptions {
+            exceptions: vec!["https://example.com/*".to_string()],
+            ..CheckOptions::default()
+        };
+        let report = check_one(&l, &options);
+        assert_eq!(report.status, LinkStatus::Skipped);
+    }
+
+    #[test]
+    fn literal_exception_matches_exact_url() {
+        assert!(is_exception(
+            "https://example.com",
+            &["https://example.com".to_string()]
+        ));
+        assert!(!is_exception(
+            "https://example.com/other",
+            &["https://example.com".to_string()]
+        ));
+    }
+
+    /// Tests that a `mailto:` link is reported as `Skipped` rather than checked as a
+    /// filesystem path (it will never resolve to a file on disk).
+    #[test]
+    fn mailto_link_is_skipped_not_broken() {
+        let l = link("mailto:me@example.com", "file.md");
+        let report = check_one(&l, &CheckOptions::default());
+        assert_eq!(report.status, LinkStatus::Skipped);
+    }
+
+    /// Tests that `LinkReport` serializes to a CSV record instead of erroring, guarding
+    /// against the `csv` crate's lack of support for nested/flattened structs.
+    #[test]
+    fn link_report_serializes_to_csv() {
+        let l = link("does_not_exist.md", "test_markdown/no_links.md");
+        let report = check_filesystem(&l);
+
+        let mut wtr = csv::WriterBuilder::new().from_writer(vec![]);
+        wtr.serialize(&report).unwrap();
+        let csv_output = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+        assert!(csv_output.contains("does_not_exist.md"));
+        assert!(csv_output.contains("Broken"));
+    }
+}
@@ -2,8 +2,12 @@ use clap::{Parser, ValueHint};
 use csv::WriterBuilder;
 use std::{fs::File, io::Read};
 
+mod checker;
 mod parser;
+mod walker;
+use crate::checker::{check_links, CheckOptions};
 use crate::parser::*;
+use crate::walker::discover_files;
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -24,6 +28,42 @@ struct Args {
     /// Field separator
     #[clap(short, long, default_value = ",")]
     separator: String,
+
+    /// Walk directories recursively, honoring .gitignore/.ignore files
+    #[clap(short, long)]
+    recursive: bool,
+
+    /// Glob pattern(s) used to filter files found while walking directories (repeatable)
+    #[clap(long, default_value = "*.md,*.markdown", value_delimiter = ',')]
+    glob: Vec<String>,
+
+    /// Keep links nested inside code blocks, inline code spans, and raw HTML (their content is not parsed, so links written there are not extracted either way)
+    #[clap(long)]
+    include_code_links: bool,
+
+    /// Also extract bare URLs from plain text, not just markdown [text](url) links
+    #[clap(long)]
+    bare_urls: bool,
+
+    /// Only report links that point outside the referencing document (network scheme or host)
+    #[clap(long, conflicts_with = "internal_only")]
+    external_only: bool,
+
+    /// Only report links that are relative/local to the referencing document
+    #[clap(long)]
+    internal_only: bool,
+
+    /// Validate extracted links and report Ok/Broken/Skipped instead of listing them
+    #[clap(long)]
+    check: bool,
+
+    /// Glob pattern or literal URL that should never be reported as broken (repeatable)
+    #[clap(long = "check-except")]
+    check_exceptions: Vec<String>,
+
+    /// Maximum number of links checked at the same time
+    #[clap(long, default_value_t = 8)]
+    check_concurrency: usize,
 }
 
 /// Load text as `String` from filename
@@ -54,28 +94,66 @@ fn load_file(filename: &std::path::PathBuf) -> Result<String, std::io::Error> {
 /// - `Ok(links)`: `Vec` of `Link` structs. List may be empty if no links in file.
 /// - `Err(e)`: Error result. Handle this!
 ///
-fn parse_from_filename(filename: &std::path::PathBuf) -> Result<Vec<parser::Link>, std::io::Error> {
+fn parse_from_filename(
+    filename: &std::path::PathBuf,
+    include_verbatim: bool,
+    extract_bare_urls: bool,
+) -> Result<Vec<parser::Link>, std::io::Error> {
     let contents = load_file(filename)?;
-    let links = extract_links(&contents, &filename.to_string_lossy());
+    let links = extract_links(
+        &contents,
+        &filename.to_string_lossy(),
+        include_verbatim,
+        extract_bare_urls,
+    );
     Ok(links)
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
-    let link_list: Vec<parser::Link> = args
-        .filenames
+    let files = discover_files(&args.filenames, args.recursive, &args.glob);
+    let link_list: Vec<parser::Link> = files
         .iter()
-        .filter_map(|filename| match parse_from_filename(filename) {
-            Ok(links) => Some(links),
-            Err(e) => {
-                eprintln!("Error parsing file {:?}: {}", filename, e);
-                None
+        .filter_map(|filename| {
+            match parse_from_filename(filename, args.include_code_links, args.bare_urls) {
+                Ok(links) => Some(links),
+                Err(e) => {
+                    eprintln!("Error parsing file {:?}: {}", filename, e);
+                    None
+                }
             }
         })
         .flat_map(|links| links.into_iter())
+        .filter(|link| {
+            if args.external_only {
+                link.is_external
+            } else if args.internal_only {
+                !link.is_external
+            } else {
+                true
+            }
+        })
         .collect();
 
-    if args.json {
+    if args.check {
+        let options = CheckOptions {
+            exceptions: args.check_exceptions,
+            max_concurrency: args.check_concurrency,
+        };
+        let reports = check_links(&link_list, &options);
+
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&reports)?);
+        } else {
+            let mut wtr = WriterBuilder::new()
+                .delimiter(args.separator.as_bytes()[0])
+                .from_writer(std::io::stdout());
+            for report in reports {
+                wtr.serialize(report)?;
+            }
+            wtr.flush()?;
+        }
+    } else if args.json {
         // json serializer
         println!("{}", serde_json::to_string_pretty(&link_list)?);
     } else {
@@ -121,7 +199,7 @@ mod tests {
     #[test]
     fn parse_markdown_with_links() {
         let filepath = std::path::PathBuf::from("test_markdown/three_links.md");
-        let links = parse_from_filename(&filepath).unwrap();
+        let links = parse_from_filename(&filepath, false, false).unwrap();
         assert_eq!(links.len(), 3);
         assert_eq!(links[0].url, "https://example.com");
         assert!(links[0].description.contains("three links: a"))
@@ -133,7 +211,7 @@ mod tests {
     #[test]
     fn parse_markdown_without_links() {
         let filepath = std::path::PathBuf::from("test_markdown/no_links.md");
-        let links = parse_from_filename(&filepath).unwrap();
+        let links = parse_from_filename(&filepath, false, false).unwrap();
         assert_eq!(links.len(), 0)
     }
 
@@ -142,6 +220,6 @@ mod tests {
     #[test]
     fn parse_nofile_returns_err() {
         let filepath = std::path::PathBuf::from("");
-        assert!(parse_from_filename(&filepath).is_err());
+        assert!(parse_from_filename(&filepath, false, false).is_err());
     }
 }
@@ -2,7 +2,24 @@ use comrak::{
     nodes::{AstNode, NodeValue},
     parse_document, Arena, ComrakOptions,
 };
+use linkify::{LinkFinder, LinkKind as LinkifyKind};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The markdown construct a `Link` was extracted from.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum LinkKind {
+    /// A standard `[text](url)` markdown link.
+    Inline,
+    /// A bare URL found in plain prose (see `extract_bare_urls`).
+    Autolink,
+    /// An Obsidian-style `[[wikilink]]`.
+    Wikilink,
+    /// An Obsidian-style image embed, `![[wikilink]]`.
+    Image,
+}
 
 /// Represents a hyperlink extracted from a markdown document.
 ///
@@ -11,6 +28,12 @@ use serde::{Deserialize, Serialize};
 /// * `url` - A `String` containing the URL the link points to. This should be a valid URL.
 /// * `source_file` - A `String` specifying the path or name of the source file from which
 ///   the link was extracted.
+/// * `kind` - A `LinkKind` identifying which markdown construct produced the link.
+/// * `is_external` - Whether `url` points outside the referencing document (a network
+///   scheme or host), as opposed to a relative/local reference.
+/// * `resolved_target` - For relative, non-scheme URLs, the normalized path obtained by
+///   joining `url` against the directory of `source_file`. `None` for URLs that carry a
+///   scheme, a host, or are fragment-only.
 ///
 /// # Example
 /// ```
@@ -20,19 +43,129 @@ use serde::{Deserialize, Serialize};
 ///     description: "Example".to_string(),
 ///     url: "https://www.example.com".to_string(),
 ///     source_file: "file.md".to_string(),
+///     kind: LinkKind::Inline,
+///     is_external: true,
+///     resolved_target: None,
 /// };
 ///
 /// // Example of serializing the `Link` struct to a JSON string
 /// let serialized_link = to_string(&link).unwrap();
 /// println!("{}", serialized_link);
 ///
-/// // Output: {"description":"Example","url":"https://www.example.com","source_file":"file.md"}
+/// // Output: {"description":"Example","url":"https://www.example.com","source_file":"file.md","kind":"Inline","is_external":true,"resolved_target":null}
 /// ```
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Link {
     pub description: String,
     pub url: String,
     pub source_file: String,
+    pub kind: LinkKind,
+    pub is_external: bool,
+    pub resolved_target: Option<String>,
+}
+
+/// Returns `true` if `url` carries a network scheme (`http:`, `mailto:`, ...) or an
+/// explicit host (`//example.com/...`), making it external to the referencing document.
+/// Fragment-only references (`#section`) and relative/local paths are internal, following
+/// the distinction tools like hyperlink draw for their external-link dump.
+pub fn is_external_url(url: &str) -> bool {
+    if url.is_empty() || url.starts_with('#') {
+        return false;
+    }
+
+    url.starts_with("//") || has_uri_scheme(url)
+}
+
+/// Returns `true` if `url` begins with a URI scheme, i.e. a run of characters valid in a
+/// scheme name followed by `:`.
+fn has_uri_scheme(url: &str) -> bool {
+    match url.find(':') {
+        Some(colon) => {
+            let scheme = &url[..colon];
+            !scheme.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// Joins a relative, non-scheme `url` against the directory of `source_file` and
+/// normalizes the result by collapsing `.`/`..` components and using forward slashes,
+/// the way `mdbook` normalizes link targets it resolves. Returns `None` for URLs that
+/// carry a scheme or a host (see `is_external_url`) or that are fragment-only, since
+/// those have no filesystem-relative target to resolve.
+///
+/// `path_part` is percent-decoded before joining: CommonMark requires percent-encoding
+/// (or `<...>` wrapping) for spaces and other reserved characters in a link destination,
+/// so comrak hands back `./My%20Notes.md` verbatim rather than `./My Notes.md`, and the
+/// literal escaped string would never match the real file on disk.
+pub fn resolve_target(url: &str, source_file: &str) -> Option<String> {
+    if url.is_empty() || url.starts_with('#') || is_external_url(url) {
+        return None;
+    }
+
+    let path_part = url.split('#').next().unwrap_or("");
+    if path_part.is_empty() {
+        return None;
+    }
+    let decoded_path_part = percent_decode(path_part);
+
+    let base = Path::new(source_file)
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+    Some(normalize_path(&base.join(decoded_path_part)))
+}
+
+/// Decodes `%XX` percent-escape sequences in `s` into their raw bytes, the inverse of
+/// [`percent_encode_wikilink_target`]. Invalid UTF-8 left over after decoding (not
+/// expected for well-formed link targets) is replaced per `String::from_utf8_lossy`.
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 3 <= bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(
+                std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default(),
+                16,
+            ) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Collapses `.` and `..` components out of `path` and renders the result with forward
+/// slashes, without touching the filesystem (the path need not exist).
+fn normalize_path(path: &Path) -> String {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(components.last(), Some(Component::Normal(_))) {
+                    components.pop();
+                } else {
+                    components.push(component);
+                }
+            }
+            other => components.push(other),
+        }
+    }
+
+    components
+        .iter()
+        .collect::<PathBuf>()
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
 /// Extracts and concatenates all text from a given abstract syntax tree (AST) node and its descendants.
@@ -77,6 +210,20 @@ fn extract_text<'a>(root: &'a AstNode<'a>) -> String {
         .collect()
 }
 
+/// Returns `true` if `node` is nested inside a code block, inline code span, or raw
+/// HTML element, i.e. content that comrak preserves verbatim rather than rendering.
+fn in_verbatim_context<'a>(node: &'a AstNode<'a>) -> bool {
+    node.ancestors().any(|ancestor| {
+        matches!(
+            ancestor.data.borrow().value,
+            NodeValue::CodeBlock(_)
+                | NodeValue::Code(_)
+                | NodeValue::HtmlBlock(_)
+                | NodeValue::HtmlInline(_)
+        )
+    })
+}
+
 /// Convert AstNode with value NodeValue::Link into a Link. Helper function for
 /// `filter_map()`. Not recursive.
 ///
@@ -84,6 +231,8 @@ fn extract_text<'a>(root: &'a AstNode<'a>) -> String {
 ///
 /// - `node`: - Reference to AstNode produced by AstNode.children() or AstNode.descendants
 /// - `file_path`: - `&str` path to source file node was produced from.
+/// - `include_verbatim`: - `bool`. When `false` (the default), links found inside fenced or
+///   indented code blocks, inline code spans, or raw HTML are suppressed.
 ///
 /// # Returns:
 ///
@@ -95,24 +244,209 @@ fn extract_text<'a>(root: &'a AstNode<'a>) -> String {
 ///
 /// ```ignore
 ///    root.descendants()
-///        .filter_map(|node| extract_link_from_node(node, file_path))
+///        .filter_map(|node| extract_link_from_node(node, file_path, false))
 ///        .collect()
 /// ```
-fn extract_link_from_node<'a>(node: &'a AstNode<'a>, file_path: &str) -> Option<Link> {
+fn extract_link_from_node<'a>(
+    node: &'a AstNode<'a>,
+    file_path: &str,
+    include_verbatim: bool,
+) -> Option<Link> {
     if let NodeValue::Link(link) = &node.data.borrow().value {
+        if !include_verbatim && in_verbatim_context(node) {
+            return None;
+        }
+
         let url = link.url.clone();
         let title = extract_text(node);
+        let is_external = is_external_url(&url);
+        let resolved_target = resolve_target(&url, file_path);
 
         Some(Link {
             source_file: file_path.to_string(),
             description: title,
             url,
+            kind: LinkKind::Inline,
+            is_external,
+            resolved_target,
         })
     } else {
         None
     }
 }
 
+/// Returns `true` if `node` is nested inside a `NodeValue::Link`, i.e. it is part of a
+/// link's display text rather than surrounding prose.
+fn in_link_context<'a>(node: &'a AstNode<'a>) -> bool {
+    node.ancestors()
+        .any(|ancestor| matches!(ancestor.data.borrow().value, NodeValue::Link(_)))
+}
+
+/// Finds bare URLs in `text`, trimming trailing punctuation and unbalanced parentheses
+/// the way `linkify` delimits URLs in plain prose, and returns each match alongside its
+/// byte offsets into `text`.
+fn find_bare_urls(text: &str) -> Vec<(String, usize, usize)> {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkifyKind::Url]);
+    finder
+        .spans(text)
+        .filter(|span| span.kind() == Some(&LinkifyKind::Url))
+        .map(|span| (span.as_str().to_string(), span.start(), span.end()))
+        .collect()
+}
+
+/// Returns the sentence in `text` containing the byte range `start..end`, trimmed of
+/// surrounding whitespace. Sentence boundaries are `.`, `!`, `?`, and newlines.
+fn surrounding_sentence(text: &str, start: usize, end: usize) -> String {
+    let sentence_start = text[..start]
+        .rfind(['.', '!', '?', '\n'])
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let sentence_end = text[end..]
+        .find(['.', '!', '?', '\n'])
+        .map(|i| end + i + 1)
+        .unwrap_or(text.len());
+    text[sentence_start..sentence_end].trim().to_string()
+}
+
+/// Finds bare URLs within a `NodeValue::Text` node and converts each into a `Link`,
+/// skipping text that is inside a verbatim context (unless `include_verbatim` is set),
+/// that is itself a link's display text, or that falls inside a `[[wikilink]]` run (those
+/// are handled by `extract_wikilinks_from_node` instead, and a `[[http://example.com]]`
+/// wikilink would otherwise also be picked up here as a second, duplicate `Autolink`).
+fn extract_bare_urls_from_node<'a>(
+    node: &'a AstNode<'a>,
+    file_path: &str,
+    include_verbatim: bool,
+) -> Vec<Link> {
+    let NodeValue::Text(text) = &node.data.borrow().value else {
+        return Vec::new();
+    };
+
+    if (!include_verbatim && in_verbatim_context(node)) || in_link_context(node) {
+        return Vec::new();
+    }
+
+    let wikilink_spans: Vec<(usize, usize)> = wikilink_run_pattern()
+        .find_iter(text)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+
+    find_bare_urls(text)
+        .into_iter()
+        .filter(|(_, start, end)| {
+            !wikilink_spans
+                .iter()
+                .any(|(span_start, span_end)| *start >= *span_start && *end <= *span_end)
+        })
+        .map(|(url, start, end)| {
+            let is_external = is_external_url(&url);
+            let resolved_target = resolve_target(&url, file_path);
+            Link {
+                source_file: file_path.to_string(),
+                description: surrounding_sentence(text, start, end),
+                url,
+                kind: LinkKind::Autolink,
+                is_external,
+                resolved_target,
+            }
+        })
+        .collect()
+}
+
+/// Regex matching an Obsidian wikilink or image embed run, e.g. `[[note#heading|label]]`
+/// or `![[image.png]]`. Group 1 captures the leading `!` for image embeds; group 2
+/// captures the content between the double brackets.
+fn wikilink_run_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(!)?\[\[([^\[\]]+)\]\]").unwrap())
+}
+
+/// Regex decomposing the content of a wikilink run into its `file`, `block` (heading
+/// anchor), and `label` (display text) components.
+fn wikilink_content_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(?P<file>[^#|]+)(#(?P<block>.+?))?(\|(?P<label>.+?))?$").unwrap()
+    })
+}
+
+/// Percent-encodes bytes outside of a small set of characters safe to leave bare in a
+/// path-like target, matching the way Obsidian exporters encode spaces and special
+/// characters in wikilink targets. `:` is left unencoded alongside the usual unreserved
+/// set so a wikilink that wraps a real URL (`[[http://example.com]]`) still produces a
+/// usable `url` rather than a broken `http%3A//example.com`.
+fn percent_encode_wikilink_target(target: &str) -> String {
+    target
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric()
+                || matches!(byte, b'-' | b'_' | b'.' | b'~' | b'/' | b':')
+            {
+                (byte as char).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// Finds Obsidian-style wikilinks and image embeds within a `NodeValue::Text` node and
+/// converts each into a `Link`, skipping text inside a verbatim context unless
+/// `include_verbatim` is set.
+fn extract_wikilinks_from_node<'a>(
+    node: &'a AstNode<'a>,
+    file_path: &str,
+    include_verbatim: bool,
+) -> Vec<Link> {
+    let NodeValue::Text(text) = &node.data.borrow().value else {
+        return Vec::new();
+    };
+
+    if !include_verbatim && in_verbatim_context(node) {
+        return Vec::new();
+    }
+
+    wikilink_run_pattern()
+        .captures_iter(text)
+        .filter_map(|run| {
+            let is_image = run.get(1).is_some();
+            let content = run.get(2)?.as_str();
+            let parts = wikilink_content_pattern().captures(content)?;
+
+            let file = parts.name("file")?.as_str().trim();
+            let block = parts.name("block").map(|m| m.as_str().trim());
+            let label = parts.name("label").map(|m| m.as_str().trim());
+
+            let mut target = file.to_string();
+            if let Some(block) = block {
+                target.push('#');
+                target.push_str(block);
+            }
+
+            let encoded_url = percent_encode_wikilink_target(&target);
+            let is_external = is_external_url(&target);
+            // Resolve against the raw, pre-encoding `target` so the `#` that separates a
+            // heading anchor is still there to split on; `encoded_url` has already turned
+            // it into `%23` by this point.
+            let resolved_target = resolve_target(&target, file_path);
+
+            Some(Link {
+                source_file: file_path.to_string(),
+                description: label.unwrap_or(file).to_string(),
+                url: encoded_url,
+                kind: if is_image {
+                    LinkKind::Image
+                } else {
+                    LinkKind::Wikilink
+                },
+                is_external,
+                resolved_target,
+            })
+        })
+        .collect()
+}
+
 /// Extracts hyperlinks from a Markdown document.
 ///
 /// Parses the given Markdown input and extracts all hyperlinks,
@@ -125,6 +459,16 @@ fn extract_link_from_node<'a>(node: &'a AstNode<'a>, file_path: &str) -> Option<
 ///
 /// - `markdown_input`: A string slice (`&str`) containing the Markdown content to be parsed.
 /// - `file_path`: A string slice (`&str`) representing the path of the Markdown file, used to resolve relative links.
+/// - `include_verbatim`: A `bool` choosing whether links nested inside fenced/indented code
+///   blocks, inline code spans, or raw HTML are dropped (`false`) or kept (`true`). Comrak
+///   stores the content of those elements as literal, unparsed text, so a link, wikilink, or
+///   bare URL written inside them is not extracted with either value.
+/// - `extract_bare_urls`: A `bool`. When `true`, naked URLs in plain prose (e.g. `Visit
+///   https://example.com`) are extracted in addition to markdown `[text](url)` links, using the
+///   surrounding sentence as `description`.
+///
+/// Obsidian-style `[[wikilink]]`s and `![[image embed]]`s are always extracted alongside
+/// markdown links; see `LinkKind`.
 ///
 /// # Output
 ///
@@ -135,20 +479,39 @@ fn extract_link_from_node<'a>(node: &'a AstNode<'a>, file_path: &str) -> Option<
 /// ```
 /// let markdown = "[OpenAI](https://openai.com)";
 /// let file_path = "/docs/my_markdown.md";
-/// let links = extract_links(markdown, file_path);
+/// let links = extract_links(markdown, file_path, false, false);
 /// assert_eq!(links.len(), 1);
 /// ```
 ///
 /// Note: The `Link` type and its structure are not defined in this documentation snippet.
-pub fn extract_links(markdown_input: &str, file_path: &str) -> Vec<Link> {
+pub fn extract_links(
+    markdown_input: &str,
+    file_path: &str,
+    include_verbatim: bool,
+    extract_bare_urls: bool,
+) -> Vec<Link> {
     let arena = Arena::new();
     let options = ComrakOptions::default();
     let root = parse_document(&arena, markdown_input, &options);
 
-    //let mut links = Vec::new();
-    root.descendants()
-        .filter_map(|node| extract_link_from_node(node, file_path))
-        .collect()
+    let mut links: Vec<Link> = root
+        .descendants()
+        .filter_map(|node| extract_link_from_node(node, file_path, include_verbatim))
+        .collect();
+
+    links.extend(
+        root.descendants()
+            .flat_map(|node| extract_wikilinks_from_node(node, file_path, include_verbatim)),
+    );
+
+    if extract_bare_urls {
+        links.extend(
+            root.descendants()
+                .flat_map(|node| extract_bare_urls_from_node(node, file_path, include_verbatim)),
+        );
+    }
+
+    links
 }
 
 #[cfg(test)]
@@ -168,9 +531,12 @@ mod tests {
             description: "example".to_string(),
             url: "https://www.example.com".to_string(),
             source_file: "file.md".to_string(),
+            kind: LinkKind::Inline,
+            is_external: true,
+            resolved_target: None,
         };
         let test_markdown = "[example](https://www.example.com)";
-        assert_eq!(vec![target], extract_links(test_markdown, "file.md"));
+        assert_eq!(vec![target], extract_links(test_markdown, "file.md", false, false));
     }
 
     /// Tests the `extract_links` function with a string containing two links.
@@ -186,17 +552,23 @@ mod tests {
                 description: "example".to_string(),
                 url: "https://www.example.com".to_string(),
                 source_file: "file.md".to_string(),
+                kind: LinkKind::Inline,
+                is_external: true,
+                resolved_target: None,
             },
             Link {
                 description: "example".to_string(),
                 url: "https://www.example.com".to_string(),
                 source_file: "file.md".to_string(),
+                kind: LinkKind::Inline,
+                is_external: true,
+                resolved_target: None,
             },
         ];
         let test_markdown = "* [example](https://www.example.com)
         *  [example](https://www.example.com) ";
-        assert_eq!(target, extract_links(test_markdown, "file.md"));
-        assert_eq!(2, extract_links(test_markdown, "file.md").len());
+        assert_eq!(target, extract_links(test_markdown, "file.md", false, false));
+        assert_eq!(2, extract_links(test_markdown, "file.md", false, false).len());
     }
 
     /// Tests the `extract_links` function with two examples of malformed markdown.
@@ -207,8 +579,8 @@ mod tests {
     fn pass_over_fake_link() {
         let test_markdown = "[example] (https://www.example.com)";
         let test_markdown2 = "(https://www.example.com)";
-        assert!(extract_links(test_markdown, "file.md").is_empty());
-        assert!(extract_links(test_markdown2, "file.md").is_empty());
+        assert!(extract_links(test_markdown, "file.md", false, false).is_empty());
+        assert!(extract_links(test_markdown2, "file.md", false, false).is_empty());
     }
 
     /// Tests the `extract_links` function with empty strings for markdown and filename.
@@ -217,7 +589,210 @@ mod tests {
     /// The function is expected to return an empty vector.
     #[test]
     fn empty_string() {
-        assert!(extract_links("", "").is_empty());
+        assert!(extract_links("", "", false, false).is_empty());
+    }
+
+    /// Tests that link-shaped text inside a fenced code block is never extracted,
+    /// regardless of `include_verbatim`, since comrak keeps code block content as a
+    /// literal, unparsed string.
+    #[test]
+    fn fenced_code_block_link_text_is_never_extracted() {
+        let test_markdown = "```\n[example](https://www.example.com)\n```";
+        assert!(extract_links(test_markdown, "file.md", false, false).is_empty());
+        assert!(extract_links(test_markdown, "file.md", true, false).is_empty());
+    }
+
+    /// Tests that a real link is still extracted when it appears next to (but not
+    /// inside) an inline code span, guarding against the verbatim check over-matching.
+    #[test]
+    fn link_beside_inline_code_is_still_extracted() {
+        let test_markdown = "See `example.rs` and [the docs](https://www.example.com)";
+        assert_eq!(1, extract_links(test_markdown, "file.md", false, false).len());
+    }
+
+    /// Tests that bare URLs in plain prose are ignored by default and extracted
+    /// when `extract_bare_urls` is `true`, with the surrounding sentence as
+    /// the description and trailing punctuation trimmed from the URL.
+    #[test]
+    fn bare_url_is_gated_behind_flag() {
+        let test_markdown = "Visit https://example.com for details. See also the docs.";
+        assert!(extract_links(test_markdown, "file.md", false, false).is_empty());
+
+        let links = extract_links(test_markdown, "file.md", false, true);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].url, "https://example.com");
+        assert_eq!(links[0].description, "Visit https://example.com for details.");
+    }
+
+    /// Tests that a bare URL is not double-counted when it also appears as the
+    /// display text of a real markdown link.
+    #[test]
+    fn bare_url_inside_link_text_is_not_duplicated() {
+        let test_markdown = "[https://example.com](https://example.com)";
+        let links = extract_links(test_markdown, "file.md", false, true);
+        assert_eq!(1, links.len());
+    }
+
+    /// Tests `is_external_url` against network-scheme URLs, host-relative URLs, and
+    /// fragment/relative references, matching the distinction `hyperlink` draws for its
+    /// dump-external-links mode.
+    #[test]
+    fn is_external_url_classifies_schemes_and_relative_paths() {
+        assert!(is_external_url("https://example.com"));
+        assert!(is_external_url("mailto:me@example.com"));
+        assert!(is_external_url("//example.com/path"));
+
+        assert!(!is_external_url("#section"));
+        assert!(!is_external_url("../docs/guide.md"));
+        assert!(!is_external_url("guide.md"));
+        assert!(!is_external_url(""));
+    }
+
+    /// Tests that `extract_links` tags inline, autolink, and wikilink URLs with the
+    /// correct `is_external` value instead of leaving it unset.
+    #[test]
+    fn extract_links_sets_is_external() {
+        let test_markdown =
+            "[remote](https://example.com) [local](./guide.md) Visit https://example.com today. [[My Note]]";
+        let links = extract_links(test_markdown, "file.md", false, true);
+
+        let remote = links.iter().find(|l| l.url == "https://example.com").unwrap();
+        assert!(remote.is_external);
+
+        let local = links.iter().find(|l| l.url == "./guide.md").unwrap();
+        assert!(!local.is_external);
+
+        let wikilink = links.iter().find(|l| l.kind == LinkKind::Wikilink).unwrap();
+        assert!(!wikilink.is_external);
+    }
+
+    /// Tests that `resolve_target` joins a relative URL against the directory of
+    /// `source_file` and normalizes away `.`/`..` components, but leaves scheme-bearing
+    /// and fragment-only URLs unresolved.
+    #[test]
+    fn resolve_target_joins_and_normalizes_relative_paths() {
+        assert_eq!(
+            resolve_target("../guide/intro.md", "docs/chapters/one.md"),
+            Some("docs/guide/intro.md".to_string())
+        );
+        assert_eq!(
+            resolve_target("./img/logo.png", "docs/index.md"),
+            Some("docs/img/logo.png".to_string())
+        );
+        assert_eq!(
+            resolve_target("guide.md", "index.md"),
+            Some("guide.md".to_string())
+        );
+
+        assert_eq!(resolve_target("https://example.com", "file.md"), None);
+        assert_eq!(resolve_target("#section", "file.md"), None);
+        assert_eq!(resolve_target("", "file.md"), None);
+    }
+
+    /// Tests that `resolve_target` still splits off a `#` heading anchor when it's given
+    /// the raw, unencoded target rather than a percent-encoded one (`%23`), guarding
+    /// against wikilink resolution baking the encoded anchor into the resolved path.
+    #[test]
+    fn resolve_target_strips_fragment_from_raw_target() {
+        assert_eq!(
+            resolve_target("Guide#Install", "vault/docs/page.md"),
+            Some("vault/docs/Guide".to_string())
+        );
+    }
+
+    /// Tests that `resolve_target` percent-decodes the destination before joining it, so a
+    /// CommonMark-escaped space (`%20`) resolves to the real on-disk filename rather than
+    /// the literal escaped string.
+    #[test]
+    fn resolve_target_percent_decodes_destination() {
+        assert_eq!(
+            resolve_target("./My%20Notes.md", "docs/index.md"),
+            Some("docs/My Notes.md".to_string())
+        );
+    }
+
+    /// Tests that `extract_links` populates `resolved_target` for a relative inline link
+    /// and leaves it `None` for an absolute one.
+    #[test]
+    fn extract_links_sets_resolved_target() {
+        let test_markdown = "[remote](https://example.com) [local](../guide/intro.md)";
+        let links = extract_links(test_markdown, "docs/chapters/one.md", false, false);
+
+        let remote = links.iter().find(|l| l.url == "https://example.com").unwrap();
+        assert_eq!(remote.resolved_target, None);
+
+        let local = links.iter().find(|l| l.url == "../guide/intro.md").unwrap();
+        assert_eq!(local.resolved_target, Some("docs/guide/intro.md".to_string()));
+    }
+
+    /// Tests that an inline link whose destination escapes a space (`./My%20Notes.md`, as
+    /// CommonMark requires) resolves `resolved_target` to the real, unescaped filename
+    /// instead of a path literally containing `%20`.
+    #[test]
+    fn extract_links_percent_decodes_resolved_target() {
+        let test_markdown = "[notes](./My%20Notes.md)";
+        let links = extract_links(test_markdown, "docs/index.md", false, false);
+        assert_eq!(
+            links[0].resolved_target,
+            Some("docs/My Notes.md".to_string())
+        );
+    }
+
+    /// Tests that a wikilink wrapping a real URL (`[[http://example.com]]`) keeps the
+    /// scheme's `:` unencoded so `url` stays a usable URL, and isn't also picked up a
+    /// second time by `extract_bare_urls_from_node` as a duplicate `Autolink`.
+    #[test]
+    fn wikilink_wrapping_url_is_not_duplicated_as_bare_url() {
+        let test_markdown = "[[http://example.com]]";
+        let links = extract_links(test_markdown, "file.md", false, true);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].url, "http://example.com");
+        assert_eq!(links[0].kind, LinkKind::Wikilink);
+    }
+
+    /// Tests parsing a plain `[[wikilink]]`: the file segment becomes the `url`, the
+    /// file name is reused as `description` when no label is given, and the link is
+    /// tagged `LinkKind::Wikilink`.
+    #[test]
+    fn plain_wikilink_is_extracted() {
+        let test_markdown = "See [[My Note]] for details.";
+        let links = extract_links(test_markdown, "file.md", false, false);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].url, "My%20Note");
+        assert_eq!(links[0].description, "My Note");
+        assert_eq!(links[0].kind, LinkKind::Wikilink);
+    }
+
+    /// Tests that a `[[note#heading|label]]` wikilink appends the heading anchor to
+    /// the url and uses the label as the description.
+    #[test]
+    fn wikilink_with_heading_and_label_is_extracted() {
+        let test_markdown = "[[Guide#Install|installation steps]]";
+        let links = extract_links(test_markdown, "file.md", false, false);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].url, "Guide%23Install");
+        assert_eq!(links[0].description, "installation steps");
+    }
+
+    /// Tests that a heading-anchored wikilink resolves `resolved_target` to the note's
+    /// path with the heading stripped, not the percent-encoded anchor baked in.
+    #[test]
+    fn wikilink_heading_resolves_target_without_encoded_fragment() {
+        let test_markdown = "[[Guide#Install]]";
+        let links = extract_links(test_markdown, "docs/page.md", false, false);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].resolved_target, Some("docs/Guide".to_string()));
+    }
+
+    /// Tests that `![[image.png]]` embeds are tagged `LinkKind::Image` rather than
+    /// `LinkKind::Wikilink`.
+    #[test]
+    fn wikilink_image_embed_is_tagged_image() {
+        let test_markdown = "![[diagram.png]]";
+        let links = extract_links(test_markdown, "file.md", false, false);
+        assert_eq!(1, links.len());
+        assert_eq!(links[0].url, "diagram.png");
+        assert_eq!(links[0].kind, LinkKind::Image);
     }
 
     /// Tests the `extract_text` function with nested markdown.
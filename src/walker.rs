@@ -0,0 +1,88 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+
+/// Builds a `GlobSet` from a list of glob patterns, silently dropping any pattern
+/// that fails to parse so a typo'd `--glob` doesn't abort the whole walk.
+fn build_glob_set(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSetBuilder::new().build().unwrap())
+}
+
+/// Walks `root` with `.gitignore`/`.ignore` awareness, returning every file whose
+/// path matches `globs`. Paths stay rooted at `root` as passed in, so relative
+/// roots produce relative `source_file` paths.
+fn walk_dir(root: &Path, globs: &GlobSet) -> Vec<PathBuf> {
+    WalkBuilder::new(root)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|ft| ft.is_file()).unwrap_or(false))
+        .map(|entry| entry.into_path())
+        .filter(|path| globs.is_match(path))
+        .collect()
+}
+
+/// Expands a list of CLI-supplied paths into a flat list of markdown files to parse.
+///
+/// Plain files are passed through unchanged. Directories are walked with the `ignore`
+/// crate's `WalkBuilder` (honoring `.gitignore`/`.ignore`) when `recursive` is set,
+/// keeping only files matching one of `globs`; when `recursive` is false a directory
+/// is passed through as-is and will surface as an error when `main` reads it, matching the
+/// existing per-file error handling in `main`.
+///
+/// # Inputs
+///
+/// - `paths`: Filenames and/or directories supplied on the command line.
+/// - `recursive`: Whether directories should be walked.
+/// - `globs`: Glob patterns used to filter files found while walking.
+///
+/// # Results
+///
+/// - `Vec<PathBuf>` of files to parse.
+pub fn discover_files(paths: &[PathBuf], recursive: bool, globs: &[String]) -> Vec<PathBuf> {
+    let glob_set = build_glob_set(globs);
+    paths
+        .iter()
+        .flat_map(|path| {
+            if recursive && path.is_dir() {
+                walk_dir(path, &glob_set)
+            } else {
+                vec![path.clone()]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_directory_paths_pass_through() {
+        let paths = vec![PathBuf::from("test_markdown/no_links.md")];
+        let discovered = discover_files(&paths, true, &["*.md".to_string()]);
+        assert_eq!(discovered, paths);
+    }
+
+    #[test]
+    fn directory_without_recursive_passes_through_unchanged() {
+        let paths = vec![PathBuf::from("test_markdown")];
+        let discovered = discover_files(&paths, false, &["*.md".to_string()]);
+        assert_eq!(discovered, paths);
+    }
+
+    #[test]
+    fn directory_with_recursive_finds_matching_files() {
+        let paths = vec![PathBuf::from("test_markdown")];
+        let discovered = discover_files(&paths, true, &["*.md".to_string()]);
+        assert!(discovered
+            .iter()
+            .all(|path| path.extension().map(|ext| ext == "md").unwrap_or(false)));
+        assert!(discovered.contains(&PathBuf::from("test_markdown/no_links.md")));
+    }
+}